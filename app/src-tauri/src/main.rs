@@ -1,9 +1,102 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::command;
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+
+use futures_util::StreamExt;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use tauri::api::notification::Notification;
+use tauri::{
+    command, AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem, Window, WindowBuilder, WindowUrl,
+};
+use tokio::sync::{oneshot, Semaphore};
+use uuid::Uuid;
+
+/// Fires an OS notification summarizing a finished generate/crawl job
+/// (blocking or streaming/rendered), pulling its text from
+/// `ApiResponse.message`/`error` (or the raw error string) so users who
+/// minimized the window still learn the job is done. A user-initiated
+/// cancellation is reported as such rather than as a failure, since hitting
+/// Stop isn't an error.
+fn notify_job_result(app: &AppHandle, title: &str, result: &Result<ApiResponse, String>) {
+    let identifier = &app.config().tauri.bundle.identifier;
+    let body = match result {
+        Ok(ApiResponse {
+            error: Some(err), ..
+        }) => format!("Failed: {err}"),
+        Ok(response) => response
+            .message
+            .clone()
+            .unwrap_or_else(|| "Completed successfully.".to_string()),
+        Err(err) if is_cancellation(err) => "Cancelled.".to_string(),
+        Err(err) => format!("Failed: {err}"),
+    };
+    let _ = Notification::new(identifier).title(title).body(body).show();
+}
+
+/// Whether `err` is the error a job command returns after `cancel_job` has
+/// stopped it, so `notify_job_result` can tell a user-initiated stop apart
+/// from a genuine failure.
+fn is_cancellation(err: &str) -> bool {
+    err.ends_with("was cancelled")
+}
+
+/// Maximum number of hidden rendering windows allowed to run concurrently.
+const MAX_CONCURRENT_RENDERS: usize = 3;
+/// How long a hidden render window is given to finish extraction before it's
+/// torn down, absent an explicit `timeout_ms` in the request params.
+const DEFAULT_RENDER_TIMEOUT_MS: u64 = 15_000;
+
+/// JS injected into the hidden window once it loads: pulls the rendered DOM,
+/// links and forms, then reports the result back over IPC.
+const EXTRACTION_SCRIPT: &str = r#"
+(function () {
+    const links = Array.from(document.querySelectorAll('a[href]')).map(a => a.href);
+    const forms = Array.from(document.querySelectorAll('form')).map(f => ({
+        action: f.action,
+        method: f.method,
+        fields: Array.from(f.elements).map(el => el.name).filter(Boolean),
+    }));
+    window.__TAURI__.invoke('report_crawl_result', {
+        jobId: '%JOB_ID%',
+        payload: { html: document.documentElement.outerHTML, links, forms },
+    });
+})();
+"#;
+
+/// Shared state backing the hidden-window crawler: a semaphore capping how
+/// many render windows may be open at once, and a table of in-flight jobs
+/// waiting on their `report_crawl_result` callback.
+struct CrawlerState {
+    limiter: Semaphore,
+    pending: StdMutex<HashMap<String, oneshot::Sender<serde_json::Value>>>,
+}
+
+impl CrawlerState {
+    fn new(max_concurrent: usize) -> Self {
+        Self {
+            limiter: Semaphore::new(max_concurrent),
+            pending: StdMutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// Closes its render window on drop, including when the window's owning
+/// future is cancelled (e.g. via `cancel_job`) rather than run to
+/// completion — otherwise an aborted crawl would leave the hidden window
+/// orphaned instead of torn down.
+struct WindowCloseGuard(Window);
+
+impl Drop for WindowCloseGuard {
+    fn drop(&mut self) {
+        let _ = self.0.close();
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ApiResponse {
@@ -12,39 +105,571 @@ struct ApiResponse {
     error: Option<String>,
 }
 
-#[command]
-async fn generate_tests(params: serde_json::Value) -> Result<ApiResponse, String> {
-    let client = Client::new();
-    let res = client
-        .post("http://localhost:8080/generate")
-        .json(&params)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TestGenerationProgress {
+    file: Option<String>,
+    test: Option<String>,
+    completed: usize,
+    total: Option<usize>,
+}
+
+/// A single decoded NDJSON line from the `/generate/stream` response: either
+/// a per-file/per-test progress update, or the final `ApiResponse`.
+enum NdjsonLine {
+    Progress(TestGenerationProgress),
+    Final(ApiResponse),
+}
 
-    res.json::<ApiResponse>()
-        .await
+/// Classifies one NDJSON line. Every field of `ApiResponse` is optional, so a
+/// progress line would also parse successfully as an (empty) `ApiResponse` —
+/// `TestGenerationProgress` is tried first because its `completed` field is
+/// required and a true final-response line won't have it.
+fn classify_ndjson_line(line: &str) -> Result<NdjsonLine, String> {
+    if let Ok(progress) = serde_json::from_str::<TestGenerationProgress>(line) {
+        return Ok(NdjsonLine::Progress(progress));
+    }
+    serde_json::from_str::<ApiResponse>(line)
+        .map(NdjsonLine::Final)
         .map_err(|e| e.to_string())
 }
 
+/// Persisted desktop-app settings for talking to the Friday backend: where it
+/// lives, an optional bearer token, and a request timeout. Loaded once at
+/// startup from the app config dir and kept in sync via `set_backend_config`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AppConfig {
+    base_url: String,
+    api_token: Option<String>,
+    #[serde(default = "default_timeout_secs")]
+    timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:8080".to_string(),
+            api_token: None,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl AppConfig {
+    fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+        let dir = app
+            .path_resolver()
+            .app_config_dir()
+            .ok_or("could not resolve app config dir")?;
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        Ok(dir.join("backend_config.json"))
+    }
+
+    fn load(app: &AppHandle) -> Self {
+        Self::config_path(app)
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, app: &AppHandle) -> Result<(), String> {
+        let path = Self::config_path(app)?;
+        let raw = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, raw).map_err(|e| e.to_string())
+    }
+
+    /// Builds a POST request against `{base_url}{path}`, applying the
+    /// configured timeout and bearer token (if any).
+    fn request(&self, client: &Client, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}{}", self.base_url.trim_end_matches('/'), path);
+        let mut builder = client
+            .post(url)
+            .timeout(Duration::from_secs(self.timeout_secs));
+        if let Some(token) = &self.api_token {
+            builder = builder.bearer_auth(token);
+        }
+        builder
+    }
+}
+
+/// Wraps the live `AppConfig` so it can be read/written behind `tauri::State`.
+struct ConfigState(StdMutex<AppConfig>);
+
+/// How a tracked job can be cancelled: a spawned-task `AbortHandle` for the
+/// two commands that run their request in a background `tokio::spawn`, or a
+/// one-shot cancel signal for the two that race their own work against it
+/// in place (so cancelling them can still run cleanup, e.g. closing a hidden
+/// render window).
+enum JobHandle {
+    Abort(tokio::task::AbortHandle),
+    Cancel(oneshot::Sender<()>),
+}
+
+/// Tracks how to cancel each in-flight `generate_tests`/`generate_tests_streaming`/
+/// `crawl_website`/`crawl_website_rendered` job by id, so `cancel_job` can stop it.
+struct JobState(StdMutex<HashMap<String, JobHandle>>);
+
+impl JobState {
+    fn new() -> Self {
+        Self(StdMutex::new(HashMap::new()))
+    }
+
+    fn register_abort(&self, job_id: &str, handle: tokio::task::AbortHandle) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(job_id.to_string(), JobHandle::Abort(handle));
+    }
+
+    fn register_cancel(&self, job_id: &str, tx: oneshot::Sender<()>) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(job_id.to_string(), JobHandle::Cancel(tx));
+    }
+
+    fn remove(&self, job_id: &str) {
+        self.0.lock().unwrap().remove(job_id);
+    }
+}
+
+/// Rejects invocations coming from a hidden crawler render window. Those
+/// windows load arbitrary, untrusted third-party URLs (see
+/// `crawl_website_rendered`) but share the same IPC bridge as every other
+/// command — without this guard, a crawled page's own JS could invoke
+/// anything the real app window can, including `get_backend_config`, which
+/// returns the configured API token. `report_crawl_result` is the one
+/// command crawler windows are meant to call, and it validates its caller
+/// separately instead of going through this.
+fn require_main_window(window: &Window) -> Result<(), String> {
+    if window.label().starts_with("crawler-") {
+        return Err("this command is not available to crawler render windows".to_string());
+    }
+    Ok(())
+}
+
+/// Cancels the in-flight job `job_id` was assigned, stopping its request (or
+/// hidden render window) and emitting `job-cancelled`.
 #[command]
-async fn crawl_website(params: serde_json::Value) -> Result<ApiResponse, String> {
-    let client = Client::new();
-    let res = client
-        .post("http://localhost:8080/crawl")
-        .json(&params)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+fn cancel_job(
+    job_id: String,
+    window: Window,
+    jobs: tauri::State<'_, JobState>,
+) -> Result<(), String> {
+    require_main_window(&window)?;
+    let handle = jobs
+        .0
+        .lock()
+        .unwrap()
+        .remove(&job_id)
+        .ok_or_else(|| format!("no in-flight job with id {job_id}"))?;
+    match handle {
+        JobHandle::Abort(handle) => handle.abort(),
+        JobHandle::Cancel(tx) => {
+            let _ = tx.send(());
+        }
+    }
+    let _ = window.emit("job-cancelled", &job_id);
+    Ok(())
+}
 
-    res.json::<ApiResponse>()
-        .await
-        .map_err(|e| e.to_string())
+#[command]
+fn get_backend_config(
+    window: Window,
+    state: tauri::State<'_, ConfigState>,
+) -> Result<AppConfig, String> {
+    require_main_window(&window)?;
+    Ok(state.0.lock().unwrap().clone())
+}
+
+#[command]
+fn set_backend_config(
+    app: AppHandle,
+    window: Window,
+    config: AppConfig,
+    state: tauri::State<'_, ConfigState>,
+) -> Result<(), String> {
+    require_main_window(&window)?;
+    config.save(&app)?;
+    *state.0.lock().unwrap() = config;
+    Ok(())
+}
+
+/// Assigns `job_id` (generated if not supplied by the caller) to this request
+/// so `cancel_job` can abort it mid-flight, then runs it to completion.
+#[command]
+async fn generate_tests(
+    app: AppHandle,
+    window: Window,
+    job_id: Option<String>,
+    params: serde_json::Value,
+    state: tauri::State<'_, ConfigState>,
+    jobs: tauri::State<'_, JobState>,
+) -> Result<ApiResponse, String> {
+    require_main_window(&window)?;
+    let job_id = job_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let config = state.0.lock().unwrap().clone();
+
+    let task = tokio::spawn(async move {
+        let client = Client::new();
+        let res = config
+            .request(&client, "/generate")
+            .json(&params)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        res.json::<ApiResponse>().await.map_err(|e| e.to_string())
+    });
+    jobs.register_abort(&job_id, task.abort_handle());
+
+    let result = task.await;
+    jobs.remove(&job_id);
+
+    let result = match result {
+        Ok(inner) => inner,
+        Err(e) if e.is_cancelled() => Err(format!("job {job_id} was cancelled")),
+        Err(e) => Err(e.to_string()),
+    };
+    notify_job_result(&app, "Test generation finished", &result);
+    result
+}
+
+/// Same as `generate_tests`, but streams NDJSON progress chunks to the frontend
+/// as `test-generation-progress` events while the request is in flight, and
+/// resolves with the final `ApiResponse` once the backend closes the stream.
+/// Assigns `job_id` (generated if not supplied) so `cancel_job` can stop it
+/// mid-stream, same as `generate_tests`, and fires a completion notification
+/// the same way once it settles.
+#[command]
+async fn generate_tests_streaming(
+    app: AppHandle,
+    window: Window,
+    job_id: Option<String>,
+    params: serde_json::Value,
+    state: tauri::State<'_, ConfigState>,
+    jobs: tauri::State<'_, JobState>,
+) -> Result<ApiResponse, String> {
+    require_main_window(&window)?;
+    let job_id = job_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let config = state.0.lock().unwrap().clone();
+
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    jobs.register_cancel(&job_id, cancel_tx);
+
+    let work = async {
+        let client = Client::new();
+        let res = config
+            .request(&client, "/generate/stream")
+            .json(&params)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mut stream = res.bytes_stream();
+        let mut buf: Vec<u8> = Vec::new();
+        let mut completed = 0usize;
+        let mut final_response: Option<ApiResponse> = None;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| e.to_string())?;
+            buf.extend_from_slice(&chunk);
+
+            while let Some(newline) = buf.iter().position(|&b| b == b'\n') {
+                // Drain only up to a literal `\n` byte and decode that complete
+                // line on its own, so a multi-byte char split across two network
+                // chunks isn't lossy-decoded before the rest of its bytes arrive.
+                let line_bytes: Vec<u8> = buf.drain(..=newline).collect();
+                let line = String::from_utf8(line_bytes).map_err(|e| e.to_string())?;
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+
+                match classify_ndjson_line(line)? {
+                    NdjsonLine::Progress(mut progress) => {
+                        completed += 1;
+                        progress.completed = completed;
+                        window
+                            .emit("test-generation-progress", &progress)
+                            .map_err(|e| e.to_string())?;
+                    }
+                    NdjsonLine::Final(response) => final_response = Some(response),
+                }
+            }
+        }
+
+        // The backend isn't guaranteed to terminate its last write with a
+        // trailing `\n`, so whatever's left in `buf` once the stream ends is
+        // one more complete line that the `\n`-delimited loop above never got
+        // a chance to drain.
+        let trailing = String::from_utf8(std::mem::take(&mut buf)).map_err(|e| e.to_string())?;
+        let trailing = trailing.trim();
+        if !trailing.is_empty() {
+            match classify_ndjson_line(trailing)? {
+                NdjsonLine::Progress(mut progress) => {
+                    completed += 1;
+                    progress.completed = completed;
+                    window
+                        .emit("test-generation-progress", &progress)
+                        .map_err(|e| e.to_string())?;
+                }
+                NdjsonLine::Final(response) => final_response = Some(response),
+            }
+        }
+
+        final_response
+            .ok_or_else(|| "backend closed the stream without a final response".to_string())
+    };
+
+    let result = tokio::select! {
+        result = work => result,
+        _ = cancel_rx => Err(format!("job {job_id} was cancelled")),
+    };
+    jobs.remove(&job_id);
+    notify_job_result(&app, "Test generation finished", &result);
+    result
+}
+
+/// Renders `params.url` in a hidden, zero-size window so JS-heavy SPAs are
+/// fully loaded before extraction, instead of forwarding raw params to the
+/// backend. Bounded by `CrawlerState::limiter` so concurrent crawl targets
+/// can't spawn unbounded windows. Assigns `job_id` (generated if not
+/// supplied) so `cancel_job` can stop it mid-render; the hidden window is
+/// closed via `WindowCloseGuard` even when cancelled. Fires a completion
+/// notification once it settles, same as `crawl_website`.
+#[command]
+async fn crawl_website_rendered(
+    app: AppHandle,
+    window: Window,
+    job_id: Option<String>,
+    params: serde_json::Value,
+    state: tauri::State<'_, CrawlerState>,
+    jobs: tauri::State<'_, JobState>,
+) -> Result<ApiResponse, String> {
+    require_main_window(&window)?;
+    let job_id = job_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    jobs.register_cancel(&job_id, cancel_tx);
+
+    let work = async {
+        let url = params
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or("params.url is required")?
+            .to_string();
+        let timeout_ms = params
+            .get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_RENDER_TIMEOUT_MS);
+
+        let parsed_url: url::Url = url.parse().map_err(|e: url::ParseError| e.to_string())?;
+        if parsed_url.scheme() != "http" && parsed_url.scheme() != "https" {
+            return Err(format!(
+                "refusing to render non-http(s) url scheme: {}",
+                parsed_url.scheme()
+            ));
+        }
+
+        let _permit = state.limiter.acquire().await.map_err(|e| e.to_string())?;
+
+        let (tx, rx) = oneshot::channel();
+
+        let label = format!("crawler-{job_id}");
+        let render_window =
+            WindowBuilder::new(&app, label.clone(), WindowUrl::External(parsed_url))
+                .visible(false)
+                .inner_size(0.0, 0.0)
+                .build()
+                .map_err(|e| e.to_string())?;
+        let _close_guard = WindowCloseGuard(render_window.clone());
+        // Only inserted once the window actually exists, so a failed `build()`
+        // above doesn't leak a `pending` entry with no window left to fill it.
+        state.pending.lock().unwrap().insert(job_id.clone(), tx);
+
+        let script = EXTRACTION_SCRIPT.replace("%JOB_ID%", &job_id);
+        let window_for_script = render_window.clone();
+        render_window.once("tauri://load", move |_| {
+            let _ = window_for_script.eval(&script);
+        });
+
+        let outcome = tokio::time::timeout(Duration::from_millis(timeout_ms), rx).await;
+
+        match outcome {
+            Ok(Ok(payload)) => Ok(ApiResponse {
+                message: Some("crawl complete".to_string()),
+                data: Some(payload),
+                error: None,
+            }),
+            Ok(Err(_)) => Err("render window closed before reporting a result".to_string()),
+            Err(_) => Err(format!("render timed out after {timeout_ms}ms")),
+        }
+    };
+
+    let result = tokio::select! {
+        result = work => result,
+        _ = cancel_rx => Err(format!("job {job_id} was cancelled")),
+    };
+    jobs.remove(&job_id);
+    state.pending.lock().unwrap().remove(&job_id);
+    notify_job_result(&app, "Website crawl finished", &result);
+    result
+}
+
+/// Called back by the JS injected into a hidden render window once it has
+/// extracted the page; resolves the matching `crawl_website_rendered` job.
+/// Only the crawler window that owns `job_id` may report its result — this
+/// is the one command a crawled (untrusted) page is allowed to reach, so the
+/// caller's window label must match the `crawler-{job_id}` it was given.
+#[command]
+fn report_crawl_result(
+    window: Window,
+    job_id: String,
+    payload: serde_json::Value,
+    state: tauri::State<'_, CrawlerState>,
+) -> Result<(), String> {
+    if window.label() != format!("crawler-{job_id}") {
+        return Err("report_crawl_result may only be called by its own crawler window".to_string());
+    }
+    if let Some(tx) = state.pending.lock().unwrap().remove(&job_id) {
+        let _ = tx.send(payload);
+    }
+    Ok(())
+}
+
+/// Assigns `job_id` (generated if not supplied by the caller) to this request
+/// so `cancel_job` can abort it mid-flight, then runs it to completion.
+#[command]
+async fn crawl_website(
+    app: AppHandle,
+    window: Window,
+    job_id: Option<String>,
+    params: serde_json::Value,
+    state: tauri::State<'_, ConfigState>,
+    jobs: tauri::State<'_, JobState>,
+) -> Result<ApiResponse, String> {
+    require_main_window(&window)?;
+    let job_id = job_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let config = state.0.lock().unwrap().clone();
+
+    let task = tokio::spawn(async move {
+        let client = Client::new();
+        let res = config
+            .request(&client, "/crawl")
+            .json(&params)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        res.json::<ApiResponse>().await.map_err(|e| e.to_string())
+    });
+    jobs.register_abort(&job_id, task.abort_handle());
+
+    let result = task.await;
+    jobs.remove(&job_id);
+
+    let result = match result {
+        Ok(inner) => inner,
+        Err(e) if e.is_cancelled() => Err(format!("job {job_id} was cancelled")),
+        Err(e) => Err(e.to_string()),
+    };
+    notify_job_result(&app, "Website crawl finished", &result);
+    result
+}
+
+/// Builds the tray menu: quick actions for the two long-running jobs plus
+/// window visibility and quit.
+fn build_tray() -> SystemTray {
+    let menu = SystemTrayMenu::new()
+        .add_item(CustomMenuItem::new("generate_tests", "Generate Tests"))
+        .add_item(CustomMenuItem::new("crawl_website", "Crawl Website"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("toggle_window", "Show/Hide"))
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(CustomMenuItem::new("quit", "Quit"));
+    SystemTray::new().with_menu(menu)
+}
+
+/// Handles tray icon clicks and menu selections. The two job items just emit
+/// an event for the frontend to act on, since `generate_tests`/`crawl_website`
+/// need params only the UI has.
+fn on_tray_event(app: &AppHandle, event: SystemTrayEvent) {
+    let window = match app.get_window("main") {
+        Some(window) => window,
+        None => return,
+    };
+
+    match event {
+        SystemTrayEvent::LeftClick { .. } => {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+        SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
+            "generate_tests" => {
+                let _ = window.show();
+                let _ = window.emit("tray-generate-tests-requested", ());
+            }
+            "crawl_website" => {
+                let _ = window.show();
+                let _ = window.emit("tray-crawl-website-requested", ());
+            }
+            "toggle_window" => {
+                if window.is_visible().unwrap_or(false) {
+                    let _ = window.hide();
+                } else {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+            "quit" => app.exit(0),
+            _ => {}
+        },
+        _ => {}
+    }
 }
 
 fn main() {
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![generate_tests, crawl_website])
+        .manage(CrawlerState::new(MAX_CONCURRENT_RENDERS))
+        .manage(JobState::new())
+        .setup(|app| {
+            let config = AppConfig::load(&app.handle());
+            app.manage(ConfigState(StdMutex::new(config)));
+            Ok(())
+        })
+        .system_tray(build_tray())
+        .on_system_tray_event(on_tray_event)
+        .invoke_handler(tauri::generate_handler![
+            generate_tests,
+            generate_tests_streaming,
+            crawl_website,
+            crawl_website_rendered,
+            report_crawl_result,
+            get_backend_config,
+            set_backend_config,
+            cancel_job
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_progress_before_final_response() {
+        let progress = classify_ndjson_line(r#"{"file":"a.rs","test":"test_foo","completed":1}"#)
+            .expect("progress line should parse");
+        assert!(matches!(progress, NdjsonLine::Progress(_)));
+
+        let final_line = classify_ndjson_line(r#"{"message":"done","data":null,"error":null}"#)
+            .expect("final response line should parse");
+        assert!(matches!(final_line, NdjsonLine::Final(_)));
+    }
+}